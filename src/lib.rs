@@ -24,6 +24,13 @@ pub enum Error {
 
     /// An attempt was made to allocate 0 bytes of memory.
     ZeroSizedAllocation,
+
+    /// An attempt was made to allocate memory with an `align` that is not a
+    /// non-zero power of two.
+    InvalidAlignment(usize),
+
+    /// No region in the `RangeSet` was able to satisfy an allocation request.
+    OutOfMemory,
 }
 
 /// An inclusive range. `RangeInclusive` doesn't implement `Copy`, so it's not
@@ -110,6 +117,44 @@ impl<const N: usize> RangeSet<N> {
         self.in_use == 0
     }
 
+    /// Checks whether `addr` is covered by some entry in this `RangeSet`.
+    pub fn contains_value(&self, addr: usize) -> bool {
+        self.find(addr).is_ok()
+    }
+
+    /// Checks whether `range` is completely covered by a single entry in
+    /// this `RangeSet`.
+    pub fn contains_range(&self, range: &Range) -> bool {
+        self.find(range.start)
+            .is_ok_and(|idx| self.ranges[idx].contains(range))
+    }
+
+    /// Checks whether `range` overlaps with any entry in this `RangeSet`,
+    /// returning the first such overlap if so.
+    pub fn intersects(&self, range: &Range) -> Option<Range> {
+        let mut idx = self.find(range.start).unwrap_or_else(|idx| idx);
+        while idx < self.in_use {
+            let entry = self.ranges[idx];
+
+            // Entries past this point start beyond `range.end`, so none of
+            // them (or any after) can overlap
+            if entry.start > range.end { break; }
+
+            if let Some(overlap) = entry.overlaps(range) {
+                return Some(overlap);
+            }
+            idx += 1;
+        }
+        None
+    }
+
+    /// Iterate over the gaps in this `RangeSet` within `bounds`, i.e. the
+    /// sub-ranges of `bounds` not covered by any entry.
+    pub fn gaps(&self, bounds: Range) -> Gaps<'_> {
+        let idx = self.find(bounds.start).unwrap_or_else(|idx| idx);
+        Gaps { entries: &self.entries()[idx..], bounds, cursor: Some(bounds.start) }
+    }
+
     /// Delete the range at `idx`
     fn delete(&mut self, idx: usize) -> Result<(), Error> {
         // Make sure we don't index out of bounds
@@ -125,26 +170,60 @@ impl<const N: usize> RangeSet<N> {
         Ok(())
     }
 
+    /// Binary search the (sorted, non-overlapping) entries for `addr`.
+    ///
+    /// Returns `Ok(idx)` if `addr` falls inside `ranges[idx]`, or
+    /// `Err(idx)` with the index at which a range starting at `addr` would
+    /// need to be inserted to keep the entries sorted.
+    fn find(&self, addr: usize) -> Result<usize, usize> {
+        self.entries().binary_search_by(|entry| {
+            if addr < entry.start {
+                cmp::Ordering::Greater
+            } else if addr > entry.end {
+                cmp::Ordering::Less
+            } else {
+                cmp::Ordering::Equal
+            }
+        })
+    }
+
     /// Insert a new range into the `RangeSet` while keeping it sorted.
     ///
     /// If the range overlaps with an existing range, both ranges will be merged
     /// into one.
     pub fn insert(&mut self, mut range: Range) -> Result<(), Error> {
-        let mut idx = 0;
+        // Binary search for the first entry that could possibly touch or
+        // overlap `range`, backing up one slot to catch an adjacent entry
+        // that ends exactly at `range.start - 1`.
+        let mut idx = self.find(range.start).unwrap_or_else(|idx| idx);
+        if idx > 0 {
+            // `eend` is `None` when `ranges[idx - 1].end == usize::MAX`, in
+            // which case it trivially touches everything after it.
+            let touches = match self.ranges[idx - 1].end.checked_add(1) {
+                Some(eend) => range.start <= eend,
+                None => true,
+            };
+            if touches { idx -= 1; }
+        }
+
         while idx < self.in_use {
             let entry = self.ranges[idx];
 
-            // Calculate this entry's end to check for touching
-            let eend = entry.end.checked_add(1).ok_or(Error::RangeSetOverflow)?;
+            // Calculate this entry's end to check for touching. `eend` is
+            // `None` when `entry.end == usize::MAX`, in which case it
+            // trivially touches everything after it.
+            let eend = entry.end.checked_add(1);
 
             // If the range starts after the current entry, continue
-            if range.start > eend {
+            if eend.is_some_and(|eend| range.start > eend) {
                 idx += 1;
                 continue;
             }
 
-            // If the ranges don't overlap/touch, break
-            if range.end < entry.start { break; }
+            // If the ranges don't overlap/touch, break. Mirror the `eend`
+            // check above: `range` touching `entry` from below (i.e.
+            // `range.end + 1 == entry.start`) still counts as touching.
+            if range.end.checked_add(1).is_some_and(|v| v < entry.start) { break; }
 
             // At this point, there is some overlap/touch: merge the ranges
             range.start = cmp::min(entry.start, range.start);
@@ -182,11 +261,17 @@ impl<const N: usize> RangeSet<N> {
         // Essentially, this remains `false` if this function call was a noop
         let mut any_removed = false;
 
-        // Go through each entry in our ranges
-        let mut idx = 0;
+        // Binary search for the first entry that could possibly overlap
+        // `range`, either the entry containing `range.start` or the first
+        // entry starting after it.
+        let mut idx = self.find(range.start).unwrap_or_else(|idx| idx);
         while idx < self.in_use {
             let entry = self.ranges[idx];
 
+            // Entries past this point start beyond `range.end`, so none of
+            // them (or any after) can overlap
+            if entry.start > range.end { break; }
+
             // If there is no overlap with this range, skip to the next entry
             if entry.overlaps(&range).is_none() {
                 idx += 1;
@@ -213,7 +298,7 @@ impl<const N: usize> RangeSet<N> {
             } else {
                 // The range is fully contained within this entry;
                 // split the entry in two and skip the new entry
-                idx += 1 * self.split_entry(idx, range)? as usize;
+                idx += self.split_entry(idx, range)? as usize;
             }
             idx += 1;
         }
@@ -264,4 +349,194 @@ impl<const N: usize> RangeSet<N> {
 
         Ok(true)
     }
+
+    /// Allocate `size` bytes, aligned to `align` bytes, from this
+    /// `RangeSet`.
+    ///
+    /// Treats this `RangeSet` as a pool of free regions. `align` must be a
+    /// non-zero power of two (pass `1` for byte alignment). The first region
+    /// able to hold an aligned allocation of `size` bytes is used; the
+    /// allocated range is carved out of this set via [`RangeSet::remove`]
+    /// and its base address is returned.
+    pub fn allocate(&mut self, size: usize, align: usize) -> Result<usize, Error> {
+        let (base, end) = self.find_fit(size, align, false)?;
+        self.remove(Range::new(base, end)?)?;
+        Ok(base)
+    }
+
+    /// Like [`RangeSet::allocate`], but scans every region and picks the one
+    /// that leaves the smallest amount of leftover space, rather than
+    /// taking the first region that fits.
+    pub fn allocate_best_fit(&mut self, size: usize, align: usize)
+            -> Result<usize, Error> {
+        let (base, end) = self.find_fit(size, align, true)?;
+        self.remove(Range::new(base, end)?)?;
+        Ok(base)
+    }
+
+    /// Find a region able to hold an `align`-aligned allocation of `size`
+    /// bytes and return its inclusive `(base, end)` bounds.
+    ///
+    /// If `best_fit` is `false`, the first region that fits is returned. If
+    /// `true`, every region is scanned and the one leaving the smallest
+    /// leftover space is chosen.
+    fn find_fit(&self, size: usize, align: usize, best_fit: bool)
+            -> Result<(usize, usize), Error> {
+        if size == 0 { return Err(Error::ZeroSizedAllocation); }
+        if align == 0 || !align.is_power_of_two() {
+            return Err(Error::InvalidAlignment(align));
+        }
+
+        let mask = align - 1;
+        let mut best: Option<(usize, usize, usize)> = None;
+
+        for entry in self.entries() {
+            // Compute the first aligned address `>= entry.start`. Overflow
+            // past `usize::MAX` means this entry can't hold the alignment.
+            let astart = match entry.start.checked_add(mask) {
+                Some(sum) => sum & !mask,
+                None => continue,
+            };
+            if astart > entry.end { continue; }
+
+            // Compute the end of the allocation, bailing out if it doesn't
+            // fit in this entry.
+            let aend = match astart.checked_add(size - 1) {
+                Some(aend) if aend <= entry.end => aend,
+                _ => continue,
+            };
+
+            if !best_fit { return Ok((astart, aend)); }
+
+            // Track the candidate leaving the least leftover space in its
+            // entry, counting both the alignment hole in front of the
+            // allocation and the space after it.
+            let leftover = (entry.end - entry.start) - (size - 1);
+            if best.is_none_or(|(_, _, bleftover)| leftover < bleftover) {
+                best = Some((astart, aend, leftover));
+            }
+        }
+
+        best.map(|(astart, aend, _)| (astart, aend)).ok_or(Error::OutOfMemory)
+    }
+
+    /// Compute the union of this `RangeSet` with `other`, inserting (and
+    /// coalescing) every entry of `other` into `self`.
+    pub fn union<const M: usize>(&mut self, other: &RangeSet<M>)
+            -> Result<(), Error> {
+        for &entry in other.entries() {
+            self.insert(entry)?;
+        }
+        Ok(())
+    }
+
+    /// Compute the intersection of this `RangeSet` with `other`, returning a
+    /// new `RangeSet` containing every overlap between an entry of `self`
+    /// and an entry of `other`.
+    ///
+    /// Since both sets are sorted and non-overlapping, this is a two-pointer
+    /// merge over both entry slices, running in `O(n + m)` rather than the
+    /// naive `O(n * m)`.
+    pub fn intersect<const M: usize>(&self, other: &RangeSet<M>)
+            -> Result<RangeSet<N>, Error> {
+        let mut result = RangeSet::new();
+
+        let a = self.entries();
+        let b = other.entries();
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() && j < b.len() {
+            if let Some(overlap) = a[i].overlaps(&b[j]) {
+                result.insert(overlap)?;
+            }
+
+            // Advance whichever entry ends first; if they end at the same
+            // point, advance `a` (the `<=` below), and `b` will naturally
+            // fall behind and get advanced on the next iteration.
+            if a[i].end <= b[j].end { i += 1; } else { j += 1; }
+        }
+
+        Ok(result)
+    }
+
+    /// Subtract `other` from this `RangeSet`, removing every entry of
+    /// `other` from `self`.
+    pub fn subtract<const M: usize>(&mut self, other: &RangeSet<M>)
+            -> Result<(), Error> {
+        for &entry in other.entries() {
+            self.remove(entry)?;
+        }
+        Ok(())
+    }
+}
+
+impl<const N: usize> Default for RangeSet<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Iterator over the gaps (uncovered sub-ranges) of a [`RangeSet`] within a
+/// bounding [`Range`], returned by [`RangeSet::gaps`].
+pub struct Gaps<'a> {
+    /// Remaining entries to walk, starting with the entry containing
+    /// `bounds.start` or, if none does, the first entry after it
+    entries: &'a [Range],
+
+    /// The bounding range gaps are computed within
+    bounds: Range,
+
+    /// Start of the next gap to search for, or `None` once exhausted
+    cursor: Option<usize>,
+}
+
+impl<'a> Iterator for Gaps<'a> {
+    type Item = Range;
+
+    fn next(&mut self) -> Option<Range> {
+        let mut cursor = self.cursor?;
+
+        loop {
+            // Skip entries that end before the cursor; they contribute
+            // nothing within `bounds`
+            while let Some(entry) = self.entries.first() {
+                if entry.end < cursor {
+                    self.entries = &self.entries[1..];
+                } else {
+                    break;
+                }
+            }
+
+            let entry = match self.entries.first() {
+                Some(entry) if entry.start <= self.bounds.end => *entry,
+                _ => {
+                    // No more covering entries within `bounds`: the final
+                    // gap, if any, runs to the end of `bounds`
+                    self.cursor = None;
+                    return (cursor <= self.bounds.end)
+                        .then(|| unsafe {
+                            Range::new_unchecked(cursor, self.bounds.end)
+                        });
+                }
+            };
+
+            if entry.start > cursor {
+                // There is a gap before this entry
+                let gap = unsafe { Range::new_unchecked(cursor, entry.start - 1) };
+                self.entries = &self.entries[1..];
+
+                // `entry.end == usize::MAX` means nothing is left past it
+                self.cursor = entry.end.checked_add(1);
+                return Some(gap);
+            }
+
+            // No gap here; this entry covers the cursor, so skip past it
+            // and keep looking. If the entry runs to `usize::MAX`, nothing
+            // is left to cover
+            self.entries = &self.entries[1..];
+            cursor = match entry.end.checked_add(1) {
+                Some(next) => next,
+                None => { self.cursor = None; return None; }
+            };
+        }
+    }
 }
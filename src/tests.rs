@@ -15,14 +15,14 @@ fn range_new_valid() {
 #[test]
 fn range_new_invalid() {
     let range = Range::new(10, 5);
-    assert_eq!(range.unwrap_err(), Error::InvalidRange);
+    assert!(matches!(range.unwrap_err(), Error::InvalidRange(_)));
 }
 
 #[test]
 fn range_contains() {
     let range1 = Range::new(5, 15).unwrap();
     let range2 = Range::new(7, 10).unwrap();
-    assert_eq!(range1.contains(&range2), true);
+    assert!(range1.contains(&range2));
 }
 
 #[test]
@@ -30,10 +30,10 @@ fn range_contains_edge_cases() {
     let range1 = Range::new(5, 15).unwrap();
 
     let range3 = Range::new(15, 15).unwrap();
-    assert_eq!(range1.contains(&range3), true);
+    assert!(range1.contains(&range3));
 
     let range4 = Range::new(16, 16).unwrap();
-    assert_eq!(range1.contains(&range4), false);
+    assert!(!range1.contains(&range4));
 }
 
 #[test]
@@ -56,14 +56,14 @@ fn range_no_overlap() {
 
 #[test]
 fn rangeset_new() {
-    let rangeset = DEFAULT_RS.clone();
+    let rangeset = DEFAULT_RS;
     assert!(rangeset.is_empty());
     assert_eq!(rangeset.entries().len(), 0);
 }
 
 #[test]
 fn rangeset_insert() {
-    let mut rangeset = DEFAULT_RS.clone();
+    let mut rangeset = DEFAULT_RS;
     rangeset.insert(Range::new(5, 15).unwrap()).unwrap();
     rangeset.insert(Range::new(20, 30).unwrap()).unwrap();
 
@@ -77,7 +77,7 @@ fn rangeset_insert() {
 
 #[test]
 fn rangeset_insert_ordering() {
-    let mut rangeset = DEFAULT_RS.clone();
+    let mut rangeset = DEFAULT_RS;
     assert_eq!(rangeset.insert(Range::new(0x1a, 0x9ffff).unwrap()), Ok(()));
     assert_eq!(rangeset.insert(Range::new(0x2, 0x9).unwrap()), Ok(()));
 
@@ -88,7 +88,7 @@ fn rangeset_insert_ordering() {
 
 #[test]
 fn rangeset_insert_overlap() {
-    let mut rangeset = DEFAULT_RS.clone();
+    let mut rangeset = DEFAULT_RS;
     rangeset.insert(Range::new(5, 15).unwrap()).unwrap();
     rangeset.insert(Range::new(10, 20).unwrap()).unwrap();
 
@@ -100,7 +100,7 @@ fn rangeset_insert_overlap() {
 
 #[test]
 fn rangeset_insert_touching() {
-    let mut rangeset = DEFAULT_RS.clone();
+    let mut rangeset = DEFAULT_RS;
     rangeset.insert(Range::new(5, 10).unwrap()).unwrap();
     rangeset.insert(Range::new(11, 15).unwrap()).unwrap();
 
@@ -110,9 +110,55 @@ fn rangeset_insert_touching() {
     assert_eq!(entries[0].end, 15);
 }
 
+#[test]
+fn rangeset_insert_touching_from_above() {
+    // Mirror of `rangeset_insert_touching`, but the new range is inserted to
+    // the left and touches the existing entry above it
+    // (`range.end + 1 == entry.start`).
+    let mut rangeset = DEFAULT_RS;
+    rangeset.insert(Range::new(11, 15).unwrap()).unwrap();
+    rangeset.insert(Range::new(5, 10).unwrap()).unwrap();
+
+    let entries = rangeset.entries();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].start, 5);
+    assert_eq!(entries[0].end, 15);
+}
+
+#[test]
+fn rangeset_insert_overlap_usize_max_entry() {
+    // An existing entry ending at `usize::MAX` has no well-defined
+    // "end + 1", but it still overlaps/touches everything above it and
+    // must merge instead of spuriously overflowing.
+    let mut rangeset = DEFAULT_RS;
+    rangeset.insert(Range::new(1000, usize::MAX).unwrap()).unwrap();
+    rangeset.insert(Range::new(500, 2000).unwrap()).unwrap();
+
+    let entries = rangeset.entries();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].start, 500);
+    assert_eq!(entries[0].end, usize::MAX);
+}
+
+#[test]
+fn rangeset_insert_refill_single_address_gap() {
+    // Splitting a range and then filling the gap back in should re-coalesce
+    // the surrounding entries into one, not leave them as two touching
+    // entries.
+    let mut rangeset = DEFAULT_RS;
+    rangeset.insert(Range::new(0, 100).unwrap()).unwrap();
+    rangeset.remove(Range::new(50, 50).unwrap()).unwrap();
+    rangeset.insert(Range::new(50, 50).unwrap()).unwrap();
+
+    let entries = rangeset.entries();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].start, 0);
+    assert_eq!(entries[0].end, 100);
+}
+
 #[test]
 fn rangeset_remove() {
-    let mut rangeset = DEFAULT_RS.clone();
+    let mut rangeset = DEFAULT_RS;
     rangeset.insert(Range::new(5, 15).unwrap()).unwrap();
     rangeset.insert(Range::new(20, 30).unwrap()).unwrap();
     let removed = rangeset.remove(Range::new(7, 10).unwrap()).unwrap();
@@ -128,7 +174,7 @@ fn rangeset_remove() {
 
 #[test]
 fn rangeset_remove_full_range() {
-    let mut rangeset = DEFAULT_RS.clone();
+    let mut rangeset = DEFAULT_RS;
     rangeset.insert(Range::new(5, 15).unwrap()).unwrap();
     let removed = rangeset.remove(Range::new(5, 15).unwrap()).unwrap();
     assert!(removed);
@@ -137,7 +183,7 @@ fn rangeset_remove_full_range() {
 
 #[test]
 fn rangeset_remove_noop() {
-    let mut rangeset = DEFAULT_RS.clone();
+    let mut rangeset = DEFAULT_RS;
     rangeset.insert(Range::new(5, 15).unwrap()).unwrap();
     let removed = rangeset.remove(Range::new(16, 20).unwrap()).unwrap();
     assert!(!removed);
@@ -151,7 +197,7 @@ fn rangeset_remove_noop() {
 
 #[test]
 fn rangeset_remove_partial_overlap() {
-    let mut rangeset = DEFAULT_RS.clone();
+    let mut rangeset = DEFAULT_RS;
     rangeset.insert(Range::new(10, 50).unwrap()).unwrap();
     rangeset.insert(Range::new(100, 150).unwrap()).unwrap();
 
@@ -168,16 +214,16 @@ fn rangeset_remove_partial_overlap() {
 
 #[test]
 fn rangeset_delete() {
-    let mut rangeset = DEFAULT_RS.clone();
+    let mut rangeset = DEFAULT_RS;
     rangeset.insert(Range::new(10, 20).unwrap()).unwrap();
 
-    assert_eq!(rangeset.delete(1).unwrap_err(), Error::IndexOutOfBounds);
+    assert!(matches!(rangeset.delete(1).unwrap_err(), Error::IndexOutOfBounds(_)));
     assert!(rangeset.delete(0).is_ok())
 }
 
 #[test]
 fn rangeset_split_entry() {
-    let mut rangeset = DEFAULT_RS.clone();
+    let mut rangeset = DEFAULT_RS;
     rangeset.insert(Range::new(10, 30).unwrap()).unwrap();
     rangeset.split_entry(0, Range::new(15, 20).unwrap()).unwrap();
 
@@ -207,7 +253,7 @@ fn rangeset_split_entry_at_max_capacity() {
 
 #[test]
 fn rangeset_split_entry_complex() {
-    let mut rangeset = DEFAULT_RS.clone();
+    let mut rangeset = DEFAULT_RS;
     rangeset.insert(Range::new(100, 300).unwrap()).unwrap();
     rangeset.split_entry(0, Range::new(150, 250).unwrap()).unwrap();
 
@@ -232,9 +278,376 @@ fn rangeset_zero_sized() {
     assert_eq!(rangeset.in_use, 0);
 }
 
+#[test]
+fn rangeset_allocate() {
+    let mut rangeset = DEFAULT_RS;
+    rangeset.insert(Range::new(0x1000, 0x1fff).unwrap()).unwrap();
+
+    let base = rangeset.allocate(0x10, 1).unwrap();
+    assert_eq!(base, 0x1000);
+
+    let entries = rangeset.entries();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0], Range { start: 0x1010, end: 0x1fff });
+}
+
+#[test]
+fn rangeset_allocate_aligned() {
+    let mut rangeset = DEFAULT_RS;
+    rangeset.insert(Range::new(0x1001, 0x2000).unwrap()).unwrap();
+
+    // The first aligned address >= 0x1001 at a 0x100 alignment is 0x1100,
+    // so the allocation should leave a hole in front of it.
+    let base = rangeset.allocate(0x10, 0x100).unwrap();
+    assert_eq!(base, 0x1100);
+
+    let entries = rangeset.entries();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0], Range { start: 0x1001, end: 0x10ff });
+    assert_eq!(entries[1], Range { start: 0x1110, end: 0x2000 });
+}
+
+#[test]
+fn rangeset_allocate_zero_sized() {
+    let mut rangeset = DEFAULT_RS;
+    rangeset.insert(Range::new(0, 0xff).unwrap()).unwrap();
+    assert_eq!(rangeset.allocate(0, 1).unwrap_err(), Error::ZeroSizedAllocation);
+}
+
+#[test]
+fn rangeset_allocate_invalid_alignment() {
+    let mut rangeset = DEFAULT_RS;
+    rangeset.insert(Range::new(0, 0xff).unwrap()).unwrap();
+    assert_eq!(rangeset.allocate(0x10, 3).unwrap_err(),
+               Error::InvalidAlignment(3));
+}
+
+#[test]
+fn rangeset_allocate_out_of_memory() {
+    let mut rangeset = DEFAULT_RS;
+    rangeset.insert(Range::new(0, 0xf).unwrap()).unwrap();
+    assert_eq!(rangeset.allocate(0x100, 1).unwrap_err(), Error::OutOfMemory);
+}
+
+#[test]
+fn rangeset_allocate_best_fit() {
+    let mut rangeset = DEFAULT_RS;
+    // The earlier, larger region would be picked by first fit, but it
+    // leaves far more leftover space than the tight region that follows
+    // (kept apart by a gap so the two don't coalesce into one entry).
+    rangeset.insert(Range::new(0x1000, 0x1fff).unwrap()).unwrap();
+    rangeset.insert(Range::new(0x3000, 0x300f).unwrap()).unwrap();
+
+    let base = rangeset.allocate_best_fit(0x10, 1).unwrap();
+    assert_eq!(base, 0x3000);
+
+    let entries = rangeset.entries();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0], Range { start: 0x1000, end: 0x1fff });
+}
+
+#[test]
+fn rangeset_union() {
+    let mut a = DEFAULT_RS;
+    a.insert(Range::new(5, 15).unwrap()).unwrap();
+
+    let mut b = DEFAULT_RS;
+    b.insert(Range::new(10, 20).unwrap()).unwrap();
+    b.insert(Range::new(100, 200).unwrap()).unwrap();
+
+    a.union(&b).unwrap();
+
+    let entries = a.entries();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0], Range { start: 5, end: 20 });
+    assert_eq!(entries[1], Range { start: 100, end: 200 });
+}
+
+#[test]
+fn rangeset_union_usize_max_entry() {
+    // Regression test: unioning in an entry that overlaps a `usize::MAX`-
+    // ending region (on either side) must merge, not spuriously overflow.
+    let mut a = DEFAULT_RS;
+    a.insert(Range::new(1000, usize::MAX).unwrap()).unwrap();
+
+    let mut b = DEFAULT_RS;
+    b.insert(Range::new(500, 2000).unwrap()).unwrap();
+
+    a.union(&b).unwrap();
+
+    let entries = a.entries();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0], Range { start: 500, end: usize::MAX });
+
+    let mut c = DEFAULT_RS;
+    c.insert(Range::new(500, 2000).unwrap()).unwrap();
+
+    let mut d = DEFAULT_RS;
+    d.insert(Range::new(1000, usize::MAX).unwrap()).unwrap();
+
+    c.union(&d).unwrap();
+
+    let entries = c.entries();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0], Range { start: 500, end: usize::MAX });
+}
+
+#[test]
+fn rangeset_intersect() {
+    let mut a = DEFAULT_RS;
+    a.insert(Range::new(5, 15).unwrap()).unwrap();
+    a.insert(Range::new(50, 60).unwrap()).unwrap();
+
+    let mut b = DEFAULT_RS;
+    b.insert(Range::new(10, 55).unwrap()).unwrap();
+
+    let c = a.intersect(&b).unwrap();
+    let entries = c.entries();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0], Range { start: 10, end: 15 });
+    assert_eq!(entries[1], Range { start: 50, end: 55 });
+}
+
+#[test]
+fn rangeset_intersect_no_overlap() {
+    let mut a = DEFAULT_RS;
+    a.insert(Range::new(5, 15).unwrap()).unwrap();
+
+    let mut b = DEFAULT_RS;
+    b.insert(Range::new(20, 30).unwrap()).unwrap();
+
+    let c = a.intersect(&b).unwrap();
+    assert!(c.is_empty());
+}
+
+#[test]
+fn rangeset_subtract() {
+    let mut a = DEFAULT_RS;
+    a.insert(Range::new(5, 50).unwrap()).unwrap();
+
+    let mut b = DEFAULT_RS;
+    b.insert(Range::new(10, 20).unwrap()).unwrap();
+
+    a.subtract(&b).unwrap();
+
+    let entries = a.entries();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0], Range { start: 5, end: 9 });
+    assert_eq!(entries[1], Range { start: 21, end: 50 });
+}
+
+#[test]
+fn rangeset_union_different_capacity() {
+    // `a` and `b` have different capacities (`N` != `M`)
+    let mut a: RangeSet<4> = RangeSet::new();
+    a.insert(Range::new(5, 15).unwrap()).unwrap();
+
+    let mut b: RangeSet<8> = RangeSet::new();
+    b.insert(Range::new(100, 200).unwrap()).unwrap();
+
+    a.union(&b).unwrap();
+
+    let entries = a.entries();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0], Range { start: 5, end: 15 });
+    assert_eq!(entries[1], Range { start: 100, end: 200 });
+}
+
+#[test]
+fn rangeset_union_overflow() {
+    // `a` is already at capacity, so merging in a disjoint entry from `b`
+    // must surface `RangeSetOverflow` rather than silently dropping it.
+    let mut a: RangeSet<1> = RangeSet::new();
+    a.insert(Range::new(5, 15).unwrap()).unwrap();
+
+    let mut b: RangeSet<4> = RangeSet::new();
+    b.insert(Range::new(100, 200).unwrap()).unwrap();
+
+    assert_eq!(a.union(&b).unwrap_err(), Error::RangeSetOverflow);
+}
+
+#[test]
+fn rangeset_intersect_different_capacity() {
+    // `a` and `b` have different capacities (`N` != `M`); the result takes
+    // `a`'s capacity.
+    let mut a: RangeSet<4> = RangeSet::new();
+    a.insert(Range::new(5, 15).unwrap()).unwrap();
+
+    let mut b: RangeSet<8> = RangeSet::new();
+    b.insert(Range::new(10, 55).unwrap()).unwrap();
+
+    let c = a.intersect(&b).unwrap();
+    let entries = c.entries();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0], Range { start: 10, end: 15 });
+}
+
+#[test]
+fn rangeset_intersect_overflow() {
+    // `a` overlaps three separate entries of `b`, but `a`'s (and thus the
+    // result's) capacity can only hold one, so the second overlap must
+    // surface `RangeSetOverflow`.
+    let mut a: RangeSet<1> = RangeSet::new();
+    a.insert(Range::new(0, 100).unwrap()).unwrap();
+
+    let mut b: RangeSet<4> = RangeSet::new();
+    b.insert(Range::new(0, 10).unwrap()).unwrap();
+    b.insert(Range::new(20, 30).unwrap()).unwrap();
+    b.insert(Range::new(50, 60).unwrap()).unwrap();
+
+    assert_eq!(a.intersect(&b).unwrap_err(), Error::RangeSetOverflow);
+}
+
+#[test]
+fn rangeset_subtract_different_capacity() {
+    // `a` and `b` have different capacities (`N` != `M`)
+    let mut a: RangeSet<4> = RangeSet::new();
+    a.insert(Range::new(5, 50).unwrap()).unwrap();
+
+    let mut b: RangeSet<8> = RangeSet::new();
+    b.insert(Range::new(10, 20).unwrap()).unwrap();
+
+    a.subtract(&b).unwrap();
+
+    let entries = a.entries();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0], Range { start: 5, end: 9 });
+    assert_eq!(entries[1], Range { start: 21, end: 50 });
+}
+
+#[test]
+fn rangeset_subtract_overflow() {
+    // `a` is already at capacity; removing the middle of its one entry
+    // would split it in two, which can't fit and must surface
+    // `RangeSetOverflow`.
+    let mut a: RangeSet<1> = RangeSet::new();
+    a.insert(Range::new(0, 100).unwrap()).unwrap();
+
+    let mut b: RangeSet<1> = RangeSet::new();
+    b.insert(Range::new(40, 60).unwrap()).unwrap();
+
+    assert_eq!(a.subtract(&b).unwrap_err(), Error::RangeSetOverflow);
+}
+
+#[test]
+fn rangeset_find() {
+    let mut rangeset = DEFAULT_RS;
+    rangeset.insert(Range::new(10, 20).unwrap()).unwrap();
+    rangeset.insert(Range::new(30, 40).unwrap()).unwrap();
+
+    assert_eq!(rangeset.find(15), Ok(0));
+    assert_eq!(rangeset.find(35), Ok(1));
+    assert_eq!(rangeset.find(0), Err(0));
+    assert_eq!(rangeset.find(25), Err(1));
+    assert_eq!(rangeset.find(100), Err(2));
+}
+
+#[test]
+fn rangeset_insert_many_non_overlapping() {
+    // Exercises the binary-search positioning in `insert` across many
+    // disjoint entries.
+    let mut rangeset = DEFAULT_RS;
+    for i in 0..100usize {
+        let base = i * 10;
+        rangeset.insert(Range::new(base, base + 5).unwrap()).unwrap();
+    }
+
+    let entries = rangeset.entries();
+    assert_eq!(entries.len(), 100);
+    assert_eq!(entries[0], Range { start: 0, end: 5 });
+    assert_eq!(entries[99], Range { start: 990, end: 995 });
+}
+
+#[test]
+fn rangeset_contains_value() {
+    let mut rangeset = DEFAULT_RS;
+    rangeset.insert(Range::new(10, 20).unwrap()).unwrap();
+
+    assert!(rangeset.contains_value(10));
+    assert!(rangeset.contains_value(15));
+    assert!(rangeset.contains_value(20));
+    assert!(!rangeset.contains_value(9));
+    assert!(!rangeset.contains_value(21));
+}
+
+#[test]
+fn rangeset_contains_range() {
+    let mut rangeset = DEFAULT_RS;
+    rangeset.insert(Range::new(10, 20).unwrap()).unwrap();
+
+    assert!(rangeset.contains_range(&Range::new(12, 18).unwrap()));
+    assert!(rangeset.contains_range(&Range::new(10, 20).unwrap()));
+    assert!(!rangeset.contains_range(&Range::new(15, 25).unwrap()));
+    assert!(!rangeset.contains_range(&Range::new(30, 40).unwrap()));
+}
+
+#[test]
+fn rangeset_contains_range_split_then_refill() {
+    // Splitting a range and then filling the hole back in re-coalesces the
+    // entries, so a query spanning the old split point must be covered.
+    let mut rangeset = DEFAULT_RS;
+    rangeset.insert(Range::new(0, 100).unwrap()).unwrap();
+    rangeset.remove(Range::new(50, 50).unwrap()).unwrap();
+    rangeset.insert(Range::new(50, 50).unwrap()).unwrap();
+
+    assert!(rangeset.contains_range(&Range::new(10, 80).unwrap()));
+}
+
+#[test]
+fn rangeset_intersects() {
+    let mut rangeset = DEFAULT_RS;
+    rangeset.insert(Range::new(10, 20).unwrap()).unwrap();
+    rangeset.insert(Range::new(30, 40).unwrap()).unwrap();
+
+    let overlap = rangeset.intersects(&Range::new(15, 35).unwrap());
+    assert_eq!(overlap, Some(Range { start: 15, end: 20 }));
+
+    assert_eq!(rangeset.intersects(&Range::new(21, 29).unwrap()), None);
+}
+
+#[test]
+fn rangeset_gaps() {
+    let mut rangeset = DEFAULT_RS;
+    rangeset.insert(Range::new(10, 20).unwrap()).unwrap();
+    rangeset.insert(Range::new(30, 40).unwrap()).unwrap();
+
+    let mut gaps = rangeset.gaps(Range::new(0, 50).unwrap());
+    assert_eq!(gaps.next(), Some(Range { start: 0, end: 9 }));
+    assert_eq!(gaps.next(), Some(Range { start: 21, end: 29 }));
+    assert_eq!(gaps.next(), Some(Range { start: 41, end: 50 }));
+    assert_eq!(gaps.next(), None);
+}
+
+#[test]
+fn rangeset_gaps_fully_covered() {
+    let mut rangeset = DEFAULT_RS;
+    rangeset.insert(Range::new(0, 100).unwrap()).unwrap();
+
+    let mut gaps = rangeset.gaps(Range::new(10, 20).unwrap());
+    assert_eq!(gaps.next(), None);
+}
+
+#[test]
+fn rangeset_gaps_empty_set() {
+    let rangeset = DEFAULT_RS;
+    let mut gaps = rangeset.gaps(Range::new(5, 15).unwrap());
+    assert_eq!(gaps.next(), Some(Range { start: 5, end: 15 }));
+    assert_eq!(gaps.next(), None);
+}
+
+#[test]
+fn rangeset_gaps_usize_max() {
+    let mut rangeset = DEFAULT_RS;
+    rangeset.insert(Range::new(usize::MAX - 10, usize::MAX).unwrap()).unwrap();
+
+    let mut gaps = rangeset.gaps(Range::new(usize::MAX - 20, usize::MAX).unwrap());
+    assert_eq!(gaps.next(), Some(Range { start: usize::MAX - 20, end: usize::MAX - 11 }));
+    assert_eq!(gaps.next(), None);
+}
+
 #[test]
 fn rangeset_len_edge_cases() {
-    let mut rangeset = DEFAULT_RS.clone();
+    let mut rangeset = DEFAULT_RS;
 
     // Test with no ranges (should return None)
     assert_eq!(rangeset.len(), Some(0));